@@ -1,7 +1,43 @@
+mod supervisor;
+mod tray;
+mod updater;
+mod vault;
+
+use tauri::{Emitter, Manager};
+
+/// Returns the backend's live base URL (e.g. `http://127.0.0.1:51234`) so
+/// the frontend doesn't have to hardcode a port that may already be taken.
+#[tauri::command]
+fn backend_url(app: tauri::AppHandle) -> String {
+    supervisor::backend_base_url(&app)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // Must be registered first so it can intercept a second launch
+        // before any other plugin/window setup runs.
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            log::info!("Second instance launched with args: {:?}", args);
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.unminimize();
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            let _ = app.emit("single-instance-args", args);
+        }))
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        .invoke_handler(tauri::generate_handler![
+            backend_url,
+            updater::check_for_updates,
+            vault::save_credentials,
+            vault::has_credentials,
+            vault::unlock_credentials,
+            vault::clear_credentials,
+            tray::start_timer,
+            tray::pause_timer
+        ])
         .setup(|app| {
             if cfg!(debug_assertions) {
                 app.handle().plugin(
@@ -11,27 +47,27 @@ pub fn run() {
                 )?;
             }
 
-            // In release mode, start the Python backend as a sidecar process
+            let salt_path = app
+                .path()
+                .app_local_data_dir()
+                .expect("could not resolve app local data dir")
+                .join("salt.txt");
+            std::fs::create_dir_all(salt_path.parent().unwrap()).ok();
+            app.handle()
+                .plugin(tauri_plugin_stronghold::Builder::with_argon2(&salt_path).build())?;
+            vault::init(&app.handle().clone());
+
+            // Managed unconditionally so commands like `backend_url` (and
+            // the tray's hours refresh) work in debug builds too, even
+            // though the sidecar itself is only supervised in release
+            // mode below.
+            supervisor::init(app.handle());
+
+            // In release mode, start the Python backend as a supervised sidecar process
             // In debug mode, the backend should be started manually
             #[cfg(not(debug_assertions))]
             {
-                use tauri_plugin_shell::ShellExt;
-                let shell = app.shell();
-                match shell.sidecar("binaries/backend") {
-                    Ok(sidecar) => {
-                        match sidecar.spawn() {
-                            Ok((_rx, _child)) => {
-                                log::info!("Backend sidecar started successfully");
-                            }
-                            Err(e) => {
-                                log::error!("Failed to start backend sidecar: {}", e);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        log::error!("Failed to create sidecar command: {}", e);
-                    }
-                }
+                supervisor::supervise_backend(app.handle().clone());
             }
 
             #[cfg(debug_assertions)]
@@ -39,8 +75,29 @@ pub fn run() {
                 log::info!("Debug mode: backend should be started manually with 'uvicorn app.main:app --reload'");
             }
 
+            updater::check_on_startup(app.handle().clone());
+
+            tray::init(app.handle())?;
+
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .on_window_event(|window, event| match event {
+            tauri::WindowEvent::CloseRequested { api, .. } if window.label() == "main" => {
+                // Hide instead of quitting so the sidecar (and the tray
+                // timer) keeps running in the background.
+                api.prevent_close();
+                tray::hide_to_tray(&window.app_handle());
+            }
+            tauri::WindowEvent::Destroyed => {
+                supervisor::stop_backend(&window.app_handle());
+            }
+            _ => {}
+        })
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::ExitRequested { .. } = event {
+                supervisor::stop_backend(app_handle);
+            }
+        });
 }
@@ -0,0 +1,266 @@
+//! Keeps the Python backend sidecar alive.
+//!
+//! The sidecar is expected to die occasionally (backend crash, port
+//! contention, etc.), so instead of a one-shot `spawn()` we run a small
+//! supervisor loop that restarts it with exponential backoff and reports
+//! its state to the frontend via the `backend-status` event.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_shell::process::CommandChild;
+use tauri_plugin_shell::process::CommandEvent;
+use tauri_plugin_shell::ShellExt;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const STABLE_RUN_THRESHOLD: Duration = Duration::from_secs(60);
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendStatus {
+    Starting,
+    Healthy,
+    Restarting,
+    Failed,
+}
+
+/// Managed state holding the currently-running backend process, if any,
+/// so it can be killed on app exit.
+#[derive(Default)]
+pub struct BackendProcess(pub Mutex<Option<CommandChild>>);
+
+/// Managed state holding the base URL the backend actually bound to,
+/// discovered from its stdout since the port is not fixed (`--port 0`
+/// lets the OS pick one free).
+#[derive(Default)]
+pub struct BackendUrl(pub Mutex<Option<String>>);
+
+/// Tells the supervisor loop to stop respawning the sidecar for good,
+/// e.g. while an update installer is about to overwrite the `backend`
+/// binary, or the app is shutting down. Left `false` for a plain
+/// [`kill_backend`] call, which only kills the current child and lets the
+/// loop respawn it (used to pick up fresh sidecar env vars).
+#[derive(Default)]
+pub struct BackendControl {
+    stopped: AtomicBool,
+}
+
+fn emit_status(app: &AppHandle, status: BackendStatus) {
+    log::info!("Backend status: {:?}", status);
+    if let Err(e) = app.emit("backend-status", status) {
+        log::error!("Failed to emit backend-status event: {}", e);
+    }
+}
+
+/// Registers the supervisor's managed state. Call once during app setup,
+/// regardless of whether [`supervise_backend`] actually runs in this
+/// build, so commands and background tasks that read this state (like
+/// `backend_url` or the tray's hours refresh) never hit an unmanaged-state
+/// panic in debug builds where the sidecar is started manually.
+pub fn init(app: &AppHandle) {
+    app.manage(BackendProcess::default());
+    app.manage(BackendUrl::default());
+    app.manage(BackendControl::default());
+}
+
+/// Spawns the backend sidecar and supervises it for the lifetime of the
+/// app, restarting it with exponential backoff whenever it exits
+/// unexpectedly.
+pub fn supervise_backend(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut backoff = INITIAL_BACKOFF;
+
+        loop {
+            if app.state::<BackendControl>().stopped.load(Ordering::SeqCst) {
+                log::info!("Backend supervisor stopped, not respawning");
+                break;
+            }
+
+            emit_status(
+                &app,
+                if backoff == INITIAL_BACKOFF {
+                    BackendStatus::Starting
+                } else {
+                    BackendStatus::Restarting
+                },
+            );
+
+            *app.state::<BackendUrl>().0.lock().unwrap() = None;
+
+            let shell = app.shell();
+            let sidecar = match shell.sidecar("binaries/backend") {
+                Ok(sidecar) => sidecar
+                    .args(["--port", "0"])
+                    .envs(crate::vault::sidecar_env(&app)),
+                Err(e) => {
+                    log::error!("Failed to create sidecar command: {}", e);
+                    emit_status(&app, BackendStatus::Failed);
+                    sleep_and_grow(&mut backoff).await;
+                    continue;
+                }
+            };
+
+            let (mut rx, child) = match sidecar.spawn() {
+                Ok(pair) => pair,
+                Err(e) => {
+                    log::error!("Failed to start backend sidecar: {}", e);
+                    emit_status(&app, BackendStatus::Failed);
+                    sleep_and_grow(&mut backoff).await;
+                    continue;
+                }
+            };
+            *app.state::<BackendProcess>().0.lock().unwrap() = Some(child);
+
+            let started_at = tokio::time::Instant::now();
+            let mut health_check = tokio::time::interval(HEALTH_POLL_INTERVAL);
+            let mut reported_healthy = false;
+
+            loop {
+                tokio::select! {
+                    event = rx.recv() => {
+                        match event {
+                            Some(CommandEvent::Terminated(payload)) => {
+                                log::warn!("Backend sidecar terminated: {:?}", payload);
+                                break;
+                            }
+                            Some(CommandEvent::Error(err)) => {
+                                log::error!("Backend sidecar error: {}", err);
+                                break;
+                            }
+                            Some(CommandEvent::Stdout(line)) => {
+                                let line = String::from_utf8_lossy(&line);
+                                log::debug!("backend: {}", line);
+                                if let Some(port) = parse_bound_port(&line) {
+                                    let url = format!("http://127.0.0.1:{}", port);
+                                    log::info!("Backend bound to {}", url);
+                                    *app.state::<BackendUrl>().0.lock().unwrap() = Some(url);
+                                }
+                            }
+                            Some(CommandEvent::Stderr(line)) => {
+                                log::debug!("backend: {}", String::from_utf8_lossy(&line));
+                            }
+                            Some(_) => {}
+                            None => break,
+                        }
+                    }
+                    _ = health_check.tick() => {
+                        let base_url = app.state::<BackendUrl>().0.lock().unwrap().clone();
+                        if let Some(base_url) = base_url {
+                            if !reported_healthy && check_health(&base_url).await {
+                                reported_healthy = true;
+                                emit_status(&app, BackendStatus::Healthy);
+                            }
+                        }
+                    }
+                }
+            }
+
+            app.state::<BackendProcess>().0.lock().unwrap().take();
+
+            if started_at.elapsed() >= STABLE_RUN_THRESHOLD {
+                backoff = INITIAL_BACKOFF;
+            } else {
+                sleep_and_grow(&mut backoff).await;
+            }
+        }
+    });
+}
+
+/// Permanently stops the supervisor from respawning the sidecar, then
+/// kills the currently-running one, if any. Use this (rather than plain
+/// [`kill_backend`]) whenever nothing should come back to life after the
+/// kill — app shutdown, or an update installer about to overwrite the
+/// `backend` binary. Without the stop flag, the supervisor loop would see
+/// the killed child exit and immediately race to spawn a fresh one.
+pub fn stop_backend(app: &AppHandle) {
+    if let Some(control) = app.try_state::<BackendControl>() {
+        control.stopped.store(true, Ordering::SeqCst);
+    }
+    kill_backend(app);
+}
+
+/// Clears the stop flag set by [`stop_backend`] and respawns the
+/// supervisor loop. Use this to recover the sidecar after a [`stop_backend`]
+/// call turns out not to be permanent after all — e.g. an update download
+/// or install that failed after the backend was already stopped to free up
+/// the `backend` binary for the installer.
+pub fn resume_backend(app: &AppHandle) {
+    if let Some(control) = app.try_state::<BackendControl>() {
+        control.stopped.store(false, Ordering::SeqCst);
+    }
+    supervise_backend(app.clone());
+}
+
+/// Kills the currently-running backend sidecar, if any. Called both on app
+/// shutdown (via [`stop_backend`], so no orphaned `backend`/uvicorn process
+/// is left holding its port) and routinely by the credential vault after a
+/// save/unlock, to force a restart that picks up the freshly-changed env.
+/// The supervisor loop will see the child exit and respawn it unless the
+/// caller also wants [`stop_backend`].
+pub fn kill_backend(app: &AppHandle) {
+    let Some(state) = app.try_state::<BackendProcess>() else {
+        return;
+    };
+    let Some(child) = state.0.lock().unwrap().take() else {
+        return;
+    };
+
+    #[cfg(windows)]
+    {
+        // Kill the whole process tree so the Python interpreter and any
+        // worker subprocesses it spawned are reaped too.
+        let pid = child.pid();
+        match std::process::Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .output()
+        {
+            Ok(_) => log::info!("Backend sidecar process tree killed"),
+            Err(e) => log::error!("taskkill failed for backend sidecar: {}", e),
+        }
+        return;
+    }
+
+    #[cfg(not(windows))]
+    if let Err(e) = child.kill() {
+        log::error!("Failed to kill backend sidecar: {}", e);
+    } else {
+        log::info!("Backend sidecar killed");
+    }
+}
+
+async fn sleep_and_grow(backoff: &mut Duration) {
+    tokio::time::sleep(*backoff).await;
+    *backoff = (*backoff * 2).min(MAX_BACKOFF);
+}
+
+async fn check_health(base_url: &str) -> bool {
+    let url = format!("{}/health", base_url);
+    reqwest::get(&url)
+        .await
+        .map(|resp| resp.status().is_success())
+        .unwrap_or(false)
+}
+
+/// Parses a port number out of a uvicorn startup line such as
+/// `INFO:     Uvicorn running on http://127.0.0.1:51234 (Press CTRL+C to quit)`.
+fn parse_bound_port(line: &str) -> Option<u16> {
+    let after_scheme = line.split("http://").nth(1)?;
+    let host_and_port = after_scheme.split_whitespace().next()?;
+    let port = host_and_port.rsplit(':').next()?;
+    port.trim_end_matches('/').parse().ok()
+}
+
+/// Returns the backend's current base URL, e.g. `http://127.0.0.1:51234`,
+/// or an empty string if the backend hasn't reported its port yet.
+pub fn backend_base_url(app: &AppHandle) -> String {
+    app.state::<BackendUrl>()
+        .0
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_default()
+}
@@ -0,0 +1,196 @@
+//! System tray: a running worklog timer that keeps ticking even when the
+//! main window is hidden, plus quick pause/submit actions. The tray has no
+//! start item: timing starts through the `start_timer` command.
+//!
+//! Closing the window hides it to the tray instead of quitting, so the
+//! backend sidecar (and the timer) stays alive in the background. Only
+//! the tray's "Quit" item actually shuts the app down.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::menu::{MenuBuilder, MenuItem, MenuItemBuilder, PredefinedMenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::supervisor;
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+const TIMER_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The currently-timed issue and when it was started, shared between the
+/// tray menu and the `start_timer`/`pause_timer` commands so the main
+/// window and the tray stay in sync. `None` when no issue is being timed.
+#[derive(Default)]
+pub struct TimerState(pub Mutex<Option<(String, Instant)>>);
+
+struct TrayMenuItems {
+    status: MenuItem<tauri::Wry>,
+    today_total: MenuItem<tauri::Wry>,
+}
+
+/// Builds the tray icon and menu, and starts background tasks that refresh
+/// today's logged hours from the backend every minute and the active
+/// timer's elapsed time every 30 seconds.
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    app.manage(TimerState::default());
+
+    let status = MenuItemBuilder::with_id("status", "No issue being timed").build(app)?;
+    let today_total = MenuItemBuilder::with_id("today_total", "Today: 0.0h logged").build(app)?;
+    let pause = MenuItemBuilder::with_id("pause", "Pause timer").build(app)?;
+    let submit = MenuItemBuilder::with_id("submit", "Submit worklog").build(app)?;
+    let open = MenuItemBuilder::with_id("open", "Open dashboard").build(app)?;
+    let quit = MenuItemBuilder::with_id("quit", "Quit").build(app)?;
+
+    let menu = MenuBuilder::new(app)
+        .items(&[&status, &today_total])
+        .item(&PredefinedMenuItem::separator(app)?)
+        .items(&[&pause, &submit, &open])
+        .item(&PredefinedMenuItem::separator(app)?)
+        .item(&quit)
+        .build()?;
+
+    let tray = TrayIconBuilder::new()
+        .menu(&menu)
+        .show_menu_on_left_click(true)
+        .on_menu_event(|app, event| match event.id().as_ref() {
+            "pause" => {
+                let _ = app.emit("tray-pause-timer", ());
+            }
+            "submit" => {
+                let _ = app.emit("tray-submit-worklog", ());
+            }
+            "open" => show_main_window(app),
+            "quit" => {
+                supervisor::stop_backend(app);
+                app.exit(0);
+            }
+            _ => {}
+        })
+        .build(app)?;
+
+    app.manage(TrayMenuItems {
+        status,
+        today_total,
+    });
+    app.manage(tray);
+
+    refresh_loop(app.clone());
+    timer_refresh_loop(app.clone());
+
+    Ok(())
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+/// Hides the main window instead of closing it, so the sidecar and the
+/// timer keep running in the background.
+pub fn hide_to_tray(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+    }
+}
+
+/// Updates the tray's "currently timed issue" line. Called by the
+/// `start_timer`/`pause_timer` commands.
+pub fn set_active_issue(app: &AppHandle, issue_key: Option<&str>) {
+    let timer = issue_key.map(|key| (key.to_string(), Instant::now()));
+    let label = status_label(timer.as_ref());
+    *app.state::<TimerState>().0.lock().unwrap() = timer;
+    if let Some(items) = app.try_state::<TrayMenuItems>() {
+        let _ = items.status.set_text(label);
+    }
+}
+
+fn status_label(timer: Option<&(String, Instant)>) -> String {
+    match timer {
+        Some((key, started_at)) => {
+            format!("Timing {} ({})", key, format_elapsed(started_at.elapsed()))
+        }
+        None => "No issue being timed".to_string(),
+    }
+}
+
+/// Renders a duration as e.g. `5m` or `1h 05m`, matching the coarse
+/// granularity worklogs are tracked in.
+fn format_elapsed(elapsed: Duration) -> String {
+    let minutes = elapsed.as_secs() / 60;
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
+}
+
+/// Refreshes the status line's elapsed-time label while an issue is being
+/// timed, so the tray shows a running clock instead of a static timestamp.
+fn timer_refresh_loop(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(TIMER_REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            let timer = app.state::<TimerState>().0.lock().unwrap().clone();
+            if let Some(items) = app.try_state::<TrayMenuItems>() {
+                let _ = items.status.set_text(status_label(timer.as_ref()));
+            }
+        }
+    });
+}
+
+fn refresh_loop(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            // Safe even in debug builds where the sidecar isn't
+            // supervised: `supervisor::init` manages `BackendUrl`
+            // unconditionally, so this never hits unmanaged state.
+            let base_url = supervisor::backend_base_url(&app);
+            if base_url.is_empty() {
+                continue;
+            }
+            match fetch_today_total(&base_url).await {
+                Ok(hours) => {
+                    if let Some(items) = app.try_state::<TrayMenuItems>() {
+                        let _ = items
+                            .today_total
+                            .set_text(format!("Today: {:.1}h logged", hours));
+                    }
+                }
+                Err(e) => log::warn!("Failed to refresh today's worklog total: {}", e),
+            }
+        }
+    });
+}
+
+#[derive(serde::Deserialize)]
+struct TodayTotal {
+    hours: f64,
+}
+
+async fn fetch_today_total(base_url: &str) -> Result<f64, String> {
+    let url = format!("{}/worklog/today", base_url);
+    let total: TodayTotal = reqwest::get(&url)
+        .await
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(total.hours)
+}
+
+#[tauri::command]
+pub fn start_timer(app: AppHandle, issue_key: String) {
+    set_active_issue(&app, Some(&issue_key));
+}
+
+#[tauri::command]
+pub fn pause_timer(app: AppHandle) {
+    set_active_issue(&app, None);
+}
@@ -0,0 +1,80 @@
+//! Self-update flow for the desktop app.
+//!
+//! Updates are coordinated with the backend [`supervisor`](crate::supervisor)
+//! module: the Python sidecar is killed before a new version's installer
+//! replaces the bundled `backend` binary, and the app restarts once the
+//! install finishes so the shell and backend come back up together.
+
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_updater::UpdaterExt;
+
+use crate::supervisor;
+
+/// Checks for an update on startup and, if one is available, notifies the
+/// frontend via the `update-available` event. Does not download or
+/// install anything by itself; the frontend calls [`check_for_updates`]
+/// to proceed.
+pub fn check_on_startup(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        match app.updater() {
+            Ok(updater) => match updater.check().await {
+                Ok(Some(update)) => {
+                    log::info!("Update available: {}", update.version);
+                    let _ = app.emit("update-available", update.version.clone());
+                }
+                Ok(None) => log::info!("No update available"),
+                Err(e) => log::warn!("Update check failed: {}", e),
+            },
+            Err(e) => log::warn!("Updater unavailable: {}", e),
+        }
+    });
+}
+
+/// Downloads and installs the latest update, if any, emitting
+/// `update-progress` events as it goes. The backend sidecar is killed
+/// before the installer runs so the new binary isn't locked/in use, and
+/// the app restarts once installation completes.
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> Result<bool, String> {
+    let updater = app.updater().map_err(|e| e.to_string())?;
+    let Some(update) = updater.check().await.map_err(|e| e.to_string())? else {
+        return Ok(false);
+    };
+
+    // Stop the supervisor for good rather than just killing the current
+    // child: otherwise the loop treats the kill as an unexpected exit and
+    // races to respawn `binaries/backend` while the installer is
+    // overwriting that very file.
+    supervisor::stop_backend(&app);
+
+    let mut downloaded = 0u64;
+    let progress_app = app.clone();
+    let install_result = update
+        .download_and_install(
+            move |chunk_length, content_length| {
+                downloaded += chunk_length as u64;
+                let _ = progress_app.emit(
+                    "update-progress",
+                    serde_json::json!({
+                        "downloaded": downloaded,
+                        "total": content_length,
+                    }),
+                );
+            },
+            || {
+                log::info!("Update downloaded, installing");
+            },
+        )
+        .await;
+
+    if let Err(e) = install_result {
+        // The app isn't restarting after all, so the backend the earlier
+        // `stop_backend` shut down needs to come back or it's dead for the
+        // rest of the running process.
+        log::error!("Update install failed, resuming backend: {}", e);
+        supervisor::resume_backend(&app);
+        return Err(e.to_string());
+    }
+
+    app.restart();
+}
@@ -0,0 +1,149 @@
+//! Encrypted-at-rest storage for the Jira credentials (base URL, email,
+//! API token) behind a user passphrase, backed by Stronghold.
+//!
+//! The decrypted token never touches the app's plaintext config: it is
+//! cached in memory only, after a successful save or unlock, and handed to the
+//! backend sidecar as an environment variable at spawn time by
+//! [`supervisor`](crate::supervisor).
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::supervisor;
+
+const CLIENT_PATH: &[u8] = b"jira-credentials";
+const STORE_KEY: &str = "jira";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct JiraCredentials {
+    pub base_url: String,
+    pub email: String,
+    pub api_token: String,
+}
+
+/// Holds the credentials in memory once the vault has been unlocked, so
+/// the supervisor can pass them to sidecar restarts without re-prompting
+/// for the passphrase every time.
+#[derive(Default)]
+pub struct UnlockedCredentials(pub Mutex<Option<JiraCredentials>>);
+
+/// Registers in-memory credential state. Call once during app setup.
+pub fn init(app: &AppHandle) {
+    app.manage(UnlockedCredentials::default());
+}
+
+fn vault_path(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_local_data_dir()
+        .map(|dir| dir.join("vault.stronghold"))
+        .map_err(|e| e.to_string())
+}
+
+/// Encrypts and persists the Jira credentials behind `passphrase`, and
+/// keeps a decrypted copy in memory for the running session.
+#[tauri::command]
+pub async fn save_credentials(
+    app: AppHandle,
+    passphrase: String,
+    base_url: String,
+    email: String,
+    api_token: String,
+) -> Result<(), String> {
+    let path = vault_path(&app)?;
+    let collection = app.state::<tauri_plugin_stronghold::stronghold::StrongholdCollection>();
+    let stronghold = collection
+        .load_or_create(&app, path, passphrase)
+        .map_err(|e| e.to_string())?;
+
+    let client = stronghold
+        .load_client(CLIENT_PATH)
+        .or_else(|_| stronghold.create_client(CLIENT_PATH))
+        .map_err(|e| e.to_string())?;
+
+    let creds = JiraCredentials {
+        base_url,
+        email,
+        api_token,
+    };
+    let payload = serde_json::to_vec(&creds).map_err(|e| e.to_string())?;
+
+    client
+        .store()
+        .insert(STORE_KEY.as_bytes().to_vec(), payload, None)
+        .map_err(|e| e.to_string())?;
+
+    stronghold.save().map_err(|e| e.to_string())?;
+
+    *app.state::<UnlockedCredentials>().0.lock().unwrap() = Some(creds);
+
+    // Restart the sidecar so it picks up the freshly-saved token instead
+    // of whatever (possibly empty) env it was spawned with.
+    supervisor::kill_backend(&app);
+
+    Ok(())
+}
+
+/// Returns whether a vault file already exists on disk, regardless of
+/// whether it's currently unlocked in memory.
+#[tauri::command]
+pub fn has_credentials(app: AppHandle) -> bool {
+    vault_path(&app).map(|path| path.exists()).unwrap_or(false)
+}
+
+/// Unlocks the vault with `passphrase` and loads the credentials into
+/// memory so the supervisor can inject them into the next sidecar spawn.
+#[tauri::command]
+pub async fn unlock_credentials(app: AppHandle, passphrase: String) -> Result<(), String> {
+    let path = vault_path(&app)?;
+    let collection = app.state::<tauri_plugin_stronghold::stronghold::StrongholdCollection>();
+    let stronghold = collection
+        .load_or_create(&app, path, passphrase)
+        .map_err(|e| e.to_string())?;
+
+    let client = stronghold
+        .load_client(CLIENT_PATH)
+        .map_err(|e| e.to_string())?;
+
+    let payload = client
+        .store()
+        .get(STORE_KEY.as_bytes())
+        .map_err(|e| e.to_string())?
+        .ok_or("no credentials stored")?;
+
+    let creds: JiraCredentials = serde_json::from_slice(&payload).map_err(|e| e.to_string())?;
+    *app.state::<UnlockedCredentials>().0.lock().unwrap() = Some(creds);
+
+    // Restart the sidecar so it picks up the now-unlocked token.
+    supervisor::kill_backend(&app);
+
+    Ok(())
+}
+
+/// Wipes the persisted vault and the in-memory credentials.
+#[tauri::command]
+pub fn clear_credentials(app: AppHandle) -> Result<(), String> {
+    let path = vault_path(&app)?;
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+    app.state::<UnlockedCredentials>().0.lock().unwrap().take();
+    Ok(())
+}
+
+/// Environment variables to inject into the backend sidecar so the Jira
+/// API token reaches it without ever being written to the config file.
+/// Empty if the vault hasn't been unlocked yet.
+pub fn sidecar_env(app: &AppHandle) -> std::collections::HashMap<String, String> {
+    match app.state::<UnlockedCredentials>().0.lock().unwrap().clone() {
+        Some(creds) => std::collections::HashMap::from([
+            ("JIRA_BASE_URL".to_string(), creds.base_url),
+            ("JIRA_EMAIL".to_string(), creds.email),
+            ("JIRA_API_TOKEN".to_string(), creds.api_token),
+        ]),
+        None => std::collections::HashMap::new(),
+    }
+}
+